@@ -2,22 +2,146 @@ use crate::event::{self, Event};
 
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use string_cache::DefaultAtom as Atom;
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum BasicEncoding {
     Text,
     Json,
+    Hex,
+    Base64,
+    Logfmt,
+    Csv,
 }
 
-pub fn event_as_string(event: Event, encoding: &Option<BasicEncoding>) -> Result<String, ()> {
+/// Sits alongside `BasicEncoding` in sink configs, carrying the knobs that a
+/// handful of encodings need beyond the bare variant.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Default)]
+#[serde(default)]
+pub struct EncodingOptions {
+    /// When `true` and the resolved encoding is `Json`, fields are
+    /// serialized in lexicographic key order instead of whatever order the
+    /// event's backing map iterates in, so the same event always produces
+    /// byte-identical JSON. Defaults to `false` to preserve existing output
+    /// for sinks that don't opt in.
+    pub json_ordered: bool,
+
+    /// The column order `Csv` encodes fields in. Fields missing from the
+    /// event are encoded as an empty cell.
+    pub csv_fields: Vec<Atom>,
+}
+
+fn ordered_fields(log: &event::LogEvent) -> BTreeMap<String, event::ValueKind> {
+    log.all_fields()
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+}
+
+fn logfmt_quote(value: &str) -> String {
+    if value.contains(' ') || value.contains('=') || value.contains('"') {
+        format!("\"{}\"", value.replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn encode_logfmt(log: &event::LogEvent) -> Vec<u8> {
+    log.all_fields()
+        .into_iter()
+        .map(|(key, value)| format!("{}={}", key, logfmt_quote(&value.to_string_lossy())))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into_bytes()
+}
+
+fn csv_quote(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+static CSV_NO_FIELDS_WARNED: std::sync::Once = std::sync::Once::new();
+
+fn encode_csv(log: &event::LogEvent, columns: &[Atom]) -> Vec<u8> {
+    if columns.is_empty() {
+        CSV_NO_FIELDS_WARNED.call_once(|| {
+            warn!(
+                message = "Csv encoding configured with no csv_fields; every event will encode as an empty line.",
+            );
+        });
+    }
+
+    columns
+        .iter()
+        .map(|column| {
+            let value = log
+                .get(column)
+                .map(|v| v.to_string_lossy())
+                .unwrap_or_default();
+            csv_quote(&value)
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+        .into_bytes()
+}
+
+/// Byte-to-string adapters for the binary-safe encodings. Keeping this as a
+/// plain function pointer (rather than inlining the encoding logic into the
+/// `event_as_raw_bytes` match) lets new alphabets (e.g. base64url) be added
+/// without touching the dispatch logic.
+fn byte_encoder(encoding: &BasicEncoding) -> Option<fn(&[u8]) -> Vec<u8>> {
+    match encoding {
+        BasicEncoding::Hex => Some(|bytes| hex::encode(bytes).into_bytes()),
+        BasicEncoding::Base64 => Some(|bytes| base64::encode(bytes).into_bytes()),
+        BasicEncoding::Text | BasicEncoding::Json | BasicEncoding::Logfmt | BasicEncoding::Csv => {
+            None
+        }
+    }
+}
+
+pub fn event_as_string(
+    event: Event,
+    encoding: &Option<BasicEncoding>,
+    options: &EncodingOptions,
+) -> Result<String, ()> {
     let log = event.into_log();
 
+    if let Some(encode) = encoding.as_ref().and_then(byte_encoder) {
+        let message = log
+            .get(&event::MESSAGE)
+            .map(|v| v.as_bytes().to_vec())
+            .unwrap_or_default();
+        let bytes = encode(&message);
+        return String::from_utf8(bytes)
+            .map_err(|e| panic!("Unable to convert encoded bytes to utf8: {}", e));
+    }
+
+    match encoding {
+        Some(BasicEncoding::Logfmt) => {
+            return String::from_utf8(encode_logfmt(&log))
+                .map_err(|e| panic!("Unable to convert logfmt to utf8: {}", e));
+        }
+        Some(BasicEncoding::Csv) => {
+            return String::from_utf8(encode_csv(&log, &options.csv_fields))
+                .map_err(|e| panic!("Unable to convert csv to utf8: {}", e));
+        }
+        _ => {}
+    }
+
     if (log.is_structured() && encoding != &Some(BasicEncoding::Text))
         || encoding == &Some(BasicEncoding::Json)
     {
-        let bytes =
-            serde_json::to_vec(&log.all_fields()).map_err(|e| panic!("Error encoding: {}", e))?;
+        let bytes = if options.json_ordered {
+            serde_json::to_vec(&ordered_fields(&log))
+        } else {
+            serde_json::to_vec(&log.all_fields())
+        }
+        .map_err(|e| panic!("Error encoding: {}", e))?;
         String::from_utf8(bytes).map_err(|e| panic!("Unable to convert json to utf8: {}", e))
     } else {
         let string = log
@@ -28,30 +152,61 @@ pub fn event_as_string(event: Event, encoding: &Option<BasicEncoding>) -> Result
     }
 }
 
-pub fn event_as_bytes(event: Event, encoding: &Option<BasicEncoding>) -> Result<Bytes, ()> {
-    event_as_raw_bytes(event, encoding).map(Bytes::from)
+pub fn event_as_bytes(
+    event: Event,
+    encoding: &Option<BasicEncoding>,
+    options: &EncodingOptions,
+) -> Result<Bytes, ()> {
+    event_as_raw_bytes(event, encoding, options).map(Bytes::from)
 }
 
-pub fn event_as_bytes_with_nl(event: Event, encoding: &Option<BasicEncoding>) -> Result<Bytes, ()> {
-    event_as_raw_bytes(event, encoding).map(|mut bytes| {
+pub fn event_as_bytes_with_nl(
+    event: Event,
+    encoding: &Option<BasicEncoding>,
+    options: &EncodingOptions,
+) -> Result<Bytes, ()> {
+    event_as_raw_bytes(event, encoding, options).map(|mut bytes| {
         bytes.push(b'\n');
         Bytes::from(bytes)
     })
 }
 
-fn event_as_raw_bytes(event: Event, encoding: &Option<BasicEncoding>) -> Result<Vec<u8>, ()> {
+fn event_as_raw_bytes(
+    event: Event,
+    encoding: &Option<BasicEncoding>,
+    options: &EncodingOptions,
+) -> Result<Vec<u8>, ()> {
     let log = event.into_log();
 
+    if let Some(encode) = encoding.as_ref().and_then(byte_encoder) {
+        let message = log
+            .get(&event::MESSAGE)
+            .map(|v| v.as_bytes().to_vec())
+            .unwrap_or_default();
+        return Ok(encode(&message));
+    }
+
+    match encoding {
+        Some(BasicEncoding::Logfmt) => return Ok(encode_logfmt(&log)),
+        Some(BasicEncoding::Csv) => return Ok(encode_csv(&log, &options.csv_fields)),
+        _ => {}
+    }
+
     match (encoding, log.is_structured()) {
         (&Some(BasicEncoding::Json), _) | (_, true) => {
-            serde_json::to_vec(&log.all_fields()).map_err(|e| panic!("Error encoding: {}", e))
+            if options.json_ordered {
+                serde_json::to_vec(&ordered_fields(&log))
+            } else {
+                serde_json::to_vec(&log.all_fields())
+            }
+            .map_err(|e| panic!("Error encoding: {}", e))
         }
 
         (&Some(BasicEncoding::Text), _) | (_, false) => {
             let bytes = log
                 .get(&event::MESSAGE)
                 .map(|v| v.as_bytes().to_vec())
-                .unwrap_or(Vec::new());
+                .unwrap_or_default();
             Ok(bytes)
         }
     }
@@ -10,23 +10,30 @@ pub enum Partition {
     /// A static field that doesn't create dynamic partitions
     Static(Bytes),
 
-    /// Represents the ability to extract a key/value from the event
-    /// via the provided interpolated stream name.
-    Field(Regex, Bytes, Atom),
+    /// Represents the ability to extract one or more keys/values from the
+    /// event via the provided interpolated stream name. `Atom`s are the set
+    /// of keys referenced by the template, in the order they first appear.
+    Field(Regex, Bytes, Vec<Atom>),
 }
 
 pub fn interpolate(text: &str) -> Option<Partition> {
-    let pattern = Regex::new(r"\{\{(?P<key>\D+)\}\}").unwrap();
-
-    if let Some(cap) = pattern.captures(text.as_bytes()) {
-        if let Some(entry) = cap.name("key") {
-            return String::from_utf8(Vec::from(entry.as_bytes()))
-                .map(|key| Partition::Field(pattern, text.into(), key.into()))
-                .ok();
+    let pattern = Regex::new(r"\{\{\s*(?P<key>[^}|]+?)\s*(?:\|\|\s*(?P<default>[^}]*))?\}\}")
+        .unwrap();
+
+    let mut keys = Vec::new();
+    for cap in pattern.captures_iter(text.as_bytes()) {
+        let key = cap.name("key")?;
+        let key = String::from_utf8(Vec::from(key.as_bytes())).ok()?;
+        if !keys.contains(&Atom::from(key.as_str())) {
+            keys.push(key.into());
         }
     }
 
-    Some(Partition::Static(text.into()))
+    if keys.is_empty() {
+        Some(Partition::Static(text.into()))
+    } else {
+        Some(Partition::Field(pattern, text.into(), keys))
+    }
 }
 
 pub fn partition(event: Event, stream: &Partition) -> Option<Bytes> {
@@ -34,22 +41,33 @@ pub fn partition(event: Event, stream: &Partition) -> Option<Bytes> {
         Partition::Static(source) =>
             Some(source.clone()),
 
-        Partition::Field(pattern, source, key) => {
-            let result = event.as_log()
-                .get(&key)
-                .map(|value| {
-                    let cap = pattern.replace(source, |_cap: &Captures| value.as_bytes().clone());
-                    Bytes::from(&cap[..])
-                });
-
-            if result.is_none() {
+        Partition::Field(pattern, source, _keys) => {
+            let log = event.as_log();
+            let mut missing: Option<Atom> = None;
+
+            let result = pattern.replace_all(source, |cap: &Captures| {
+                let key = String::from_utf8_lossy(&cap["key"]).into_owned();
+                match log.get(&Atom::from(key.as_str())) {
+                    Some(value) => value.as_bytes().to_vec(),
+                    None => match cap.name("default") {
+                        Some(default) => default.as_bytes().to_vec(),
+                        None => {
+                            missing = Some(key.into());
+                            Vec::new()
+                        }
+                    },
+                }
+            });
+
+            if let Some(key) = missing {
                 warn!(
                     message = "Event key does not exist on the event and the event will be dropped.",
                     key = field::debug(key)
                 );
+                None
+            } else {
+                Some(Bytes::from(&result[..]))
             }
-
-            result
         }
     }
 }
@@ -74,12 +92,12 @@ mod tests {
         let result3 = interpolate("{{some_key}}suffix");
         let result4 = interpolate("prefix{{some_key}}suffix");
 
-        assert_eq!(result1.map(key), result2.map(key));
-        assert_eq!(result2.map(key), result3.map(key));
-        assert_eq!(result3.map(key), result4.map(key));
+        assert_eq!(result1.as_ref().map(keys), result2.as_ref().map(keys));
+        assert_eq!(result2.as_ref().map(keys), result3.as_ref().map(keys));
+        assert_eq!(result3.as_ref().map(keys), result4.as_ref().map(keys));
 
-        if let Partition::Field(_, _, key) = result1.unwrap() {
-            assert_eq!(key, "some_key".to_string());
+        if let Partition::Field(_, _, keys) = result1.unwrap() {
+            assert_eq!(keys, vec![Atom::from("some_key")]);
         } else {
             panic!("Expected Partition::Field");
         }
@@ -87,18 +105,58 @@ mod tests {
 
     #[test]
     fn interpolate_event_multiple() {
-        if let Partition::Field(_, _, key) = interpolate("{{key1}} {{key2}}").unwrap() {
-            assert_eq!(key, "some_key".to_string());
+        if let Partition::Field(_, _, keys) = interpolate("{{key1}} {{key2}}").unwrap() {
+            assert_eq!(keys, vec![Atom::from("key1"), Atom::from("key2")]);
         } else {
             panic!("Expected Partition::Field");
         }
     }
 
-    fn key(field: &Partition) -> Atom {
+    #[test]
+    fn interpolate_event_with_default() {
+        if let Partition::Field(_, _, keys) = interpolate("{{region||unknown}}").unwrap() {
+            assert_eq!(keys, vec![Atom::from("region")]);
+        } else {
+            panic!("Expected Partition::Field");
+        }
+    }
+
+    #[test]
+    fn partition_substitutes_multiple_fields() {
+        let mut event = Event::from("message");
+        event.as_mut_log().insert_explicit("region".into(), "us-east-1".into());
+        event.as_mut_log().insert_explicit("service".into(), "api".into());
+
+        let template = interpolate("logs-{{region}}-{{service||unknown}}").unwrap();
+        let result = partition(event, &template).unwrap();
+
+        assert_eq!(result, Bytes::from("logs-us-east-1-api"));
+    }
+
+    #[test]
+    fn partition_uses_default_for_missing_field() {
+        let mut event = Event::from("message");
+        event.as_mut_log().insert_explicit("region".into(), "us-east-1".into());
+
+        let template = interpolate("logs-{{region}}-{{service||unknown}}").unwrap();
+        let result = partition(event, &template).unwrap();
+
+        assert_eq!(result, Bytes::from("logs-us-east-1-unknown"));
+    }
+
+    #[test]
+    fn partition_drops_event_without_default() {
+        let event = Event::from("message");
+
+        let template = interpolate("{{region}}").unwrap();
+        assert!(partition(event, &template).is_none());
+    }
+
+    fn keys(field: &Partition) -> Vec<Atom> {
         match field {
-            Partition::Field(_, _, key) => key,
+            Partition::Field(_, _, keys) => keys.clone(),
             Partition::Static(_) => panic!("Static partitions don't have keys")
         }
     }
 
-}
\ No newline at end of file
+}
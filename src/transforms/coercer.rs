@@ -7,23 +7,48 @@ use std::str;
 use string_cache::DefaultAtom as Atom;
 use tracing::field;
 
+/// What to do with a field whose value could not be converted to the
+/// requested type. Lets pipelines trade leniency (keep the original string)
+/// for schema strictness (drop the field, or the whole event).
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CoercerFailureAction {
+    /// Leave the field with its original, unconverted value.
+    Keep,
+    /// Remove the field from the event, keeping the rest of the event.
+    DropField,
+    /// Drop the whole event.
+    DropEvent,
+}
+
+impl Default for CoercerFailureAction {
+    fn default() -> Self {
+        CoercerFailureAction::Keep
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Derivative)]
 #[serde(deny_unknown_fields, default)]
 #[derivative(Default)]
 pub struct CoercerConfig {
     pub types: HashMap<Atom, String>,
+    pub on_failure: CoercerFailureAction,
 }
 
 #[typetag::serde(name = "coercer")]
 impl crate::topology::config::TransformConfig for CoercerConfig {
     fn build(&self) -> Result<Box<dyn Transform>, String> {
         let types = parse_conversion_map(&self.types)?;
-        Ok(Box::new(Coercer { types }))
+        Ok(Box::new(Coercer {
+            types,
+            on_failure: self.on_failure.clone(),
+        }))
     }
 }
 
 pub struct Coercer {
     types: HashMap<Atom, Conversion>,
+    on_failure: CoercerFailureAction,
 }
 
 impl Transform for Coercer {
@@ -39,6 +64,13 @@ impl Transform for Coercer {
                             field = &field[..],
                             error = &field::display(err),
                         );
+                        match self.on_failure {
+                            CoercerFailureAction::Keep => {}
+                            CoercerFailureAction::DropField => {
+                                log.remove(field);
+                            }
+                            CoercerFailureAction::DropEvent => return None,
+                        }
                     }
                 }
             }
@@ -49,7 +81,7 @@ impl Transform for Coercer {
 
 #[cfg(test)]
 mod tests {
-    use super::CoercerConfig;
+    use super::{CoercerConfig, CoercerFailureAction};
     use crate::event::ValueKind;
     use crate::{topology::config::TransformConfig, Event};
     use pretty_assertions::assert_eq;
@@ -78,4 +110,74 @@ mod tests {
         assert_eq!(log[&"bool".into()], ValueKind::Boolean(true));
         assert_eq!(log[&"other".into()], ValueKind::Bytes("no".into()));
     }
+
+    #[test]
+    fn coercer_converts_timestamp_with_format() {
+        let mut event = Event::from("dummy message");
+        event.as_mut_log().insert_explicit(
+            "received_at".into(),
+            "2020-01-02T03:04:05+0000".into(),
+        );
+
+        let mut coercer = toml::from_str::<CoercerConfig>(
+            r#"
+            [types]
+            received_at = "timestamp|%Y-%m-%dT%H:%M:%S%z"
+            "#,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let log = coercer.transform(event).unwrap().into_log();
+
+        match &log[&"received_at".into()] {
+            ValueKind::Timestamp(_) => {}
+            other => panic!("Expected ValueKind::Timestamp, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn coercer_drops_field_on_failure() {
+        let mut event = Event::from("dummy message");
+        event
+            .as_mut_log()
+            .insert_explicit("number".into(), "not a number".into());
+
+        let mut coercer = toml::from_str::<CoercerConfig>(
+            r#"
+            on_failure = "drop_field"
+
+            [types]
+            number = "int"
+            "#,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let log = coercer.transform(event).unwrap().into_log();
+
+        assert!(log.get(&"number".into()).is_none());
+    }
+
+    #[test]
+    fn coercer_drops_event_on_failure() {
+        let mut event = Event::from("dummy message");
+        event
+            .as_mut_log()
+            .insert_explicit("number".into(), "not a number".into());
+
+        let mut coercer = toml::from_str::<CoercerConfig>(
+            r#"
+            on_failure = "drop_event"
+
+            [types]
+            number = "int"
+            "#,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        assert!(coercer.transform(event).is_none());
+    }
 }
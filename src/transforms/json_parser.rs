@@ -0,0 +1,155 @@
+use super::Transform;
+use crate::event::{self, Event, ValueKind};
+use serde::{Deserialize, Serialize};
+use string_cache::DefaultAtom as Atom;
+use tracing::field;
+
+#[derive(Deserialize, Serialize, Debug, Derivative)]
+#[serde(deny_unknown_fields, default)]
+#[derivative(Default)]
+pub struct JsonParserConfig {
+    #[derivative(Default(value = "event::MESSAGE.clone()"))]
+    pub field: Atom,
+    pub drop_field: bool,
+    pub prefix: Option<String>,
+}
+
+#[typetag::serde(name = "json_parser")]
+impl crate::topology::config::TransformConfig for JsonParserConfig {
+    fn build(&self) -> Result<Box<dyn Transform>, String> {
+        Ok(Box::new(JsonParser {
+            field: self.field.clone(),
+            drop_field: self.drop_field,
+            prefix: self.prefix.clone(),
+        }))
+    }
+}
+
+pub struct JsonParser {
+    field: Atom,
+    drop_field: bool,
+    prefix: Option<String>,
+}
+
+impl Transform for JsonParser {
+    fn transform(&mut self, event: Event) -> Option<Event> {
+        let mut log = event.into_log();
+
+        let parsed = log
+            .get(&self.field)
+            .and_then(|value| serde_json::from_slice::<serde_json::Value>(value.as_bytes()).ok());
+
+        match parsed {
+            Some(serde_json::Value::Object(map)) => {
+                for (key, value) in map {
+                    let key = match &self.prefix {
+                        Some(prefix) => format!("{}{}", prefix, key),
+                        None => key,
+                    };
+                    log.insert_explicit(key.into(), json_value_to_value_kind(value));
+                }
+                if self.drop_field {
+                    log.remove(&self.field);
+                }
+            }
+            _ => {
+                debug!(
+                    message = "Could not parse field as JSON.",
+                    field = &self.field[..],
+                );
+            }
+        }
+
+        Some(Event::Log(log))
+    }
+}
+
+fn json_value_to_value_kind(value: serde_json::Value) -> ValueKind {
+    match value {
+        serde_json::Value::String(s) => ValueKind::Bytes(s.into()),
+        serde_json::Value::Bool(b) => ValueKind::Boolean(b),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                ValueKind::Integer(i)
+            } else if let Some(f) = n.as_f64() {
+                ValueKind::Float(f)
+            } else {
+                ValueKind::Bytes(n.to_string().into())
+            }
+        }
+        other => ValueKind::Bytes(other.to_string().into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonParserConfig;
+    use crate::event::ValueKind;
+    use crate::{topology::config::TransformConfig, Event};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn json_parser_expands_fields() {
+        let event = Event::from(r#"{"number": 1234, "bool": true, "nested": "no"}"#);
+
+        let mut parser = toml::from_str::<JsonParserConfig>("field = \"message\"")
+            .unwrap()
+            .build()
+            .unwrap();
+        let log = parser.transform(event).unwrap().into_log();
+
+        assert_eq!(log[&"number".into()], ValueKind::Integer(1234));
+        assert_eq!(log[&"bool".into()], ValueKind::Boolean(true));
+        assert_eq!(log[&"nested".into()], ValueKind::Bytes("no".into()));
+    }
+
+    #[test]
+    fn json_parser_drops_source_field() {
+        let event = Event::from(r#"{"number": 1234}"#);
+
+        let mut parser = toml::from_str::<JsonParserConfig>(
+            r#"
+            field = "message"
+            drop_field = true
+            "#,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let log = parser.transform(event).unwrap().into_log();
+
+        assert_eq!(log[&"number".into()], ValueKind::Integer(1234));
+        assert!(log.get(&"message".into()).is_none());
+    }
+
+    #[test]
+    fn json_parser_applies_prefix() {
+        let event = Event::from(r#"{"number": 1234}"#);
+
+        let mut parser = toml::from_str::<JsonParserConfig>(
+            r#"
+            field = "message"
+            prefix = "json_"
+            "#,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let log = parser.transform(event).unwrap().into_log();
+
+        assert_eq!(log[&"json_number".into()], ValueKind::Integer(1234));
+    }
+
+    #[test]
+    fn json_parser_passes_through_on_parse_failure() {
+        let event = Event::from("not json");
+
+        let mut parser = toml::from_str::<JsonParserConfig>("field = \"message\"")
+            .unwrap()
+            .build()
+            .unwrap();
+        let log = parser.transform(event).unwrap().into_log();
+
+        assert_eq!(log[&"message".into()], ValueKind::Bytes("not json".into()));
+    }
+}
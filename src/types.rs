@@ -0,0 +1,148 @@
+use crate::event::ValueKind;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use string_cache::DefaultAtom as Atom;
+
+/// A scalar type a string field can be coerced into, as named in a
+/// `[transforms.coercer.types]` entry (e.g. `field = "int"`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conversion {
+    Bytes,
+    Integer,
+    Float,
+    Boolean,
+    /// Parses a timestamp. `None` means RFC 3339; `Some(format)` is a
+    /// strptime-style format string as accepted by `chrono`, taken from
+    /// after the `|` of the type name, e.g. `"timestamp|%Y-%m-%dT%H:%M:%S%z"`
+    /// yields `Some("%Y-%m-%dT%H:%M:%S%z")`.
+    Timestamp(Option<String>),
+}
+
+impl FromStr for Conversion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, '|');
+        let kind = parts.next().unwrap_or("").trim();
+        let format = parts.next().map(|format| format.trim().to_string());
+
+        match (kind, format) {
+            ("bytes", None) | ("string", None) => Ok(Conversion::Bytes),
+            ("int" , None) | ("integer", None) => Ok(Conversion::Integer),
+            ("float", None) => Ok(Conversion::Float),
+            ("bool", None) | ("boolean", None) => Ok(Conversion::Boolean),
+            ("timestamp", format) => Ok(Conversion::Timestamp(format)),
+            (kind, Some(_)) => Err(format!("\"{}\" conversion does not take a format", kind)),
+            (kind, None) => Err(format!("Unknown conversion type \"{}\"", kind)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ConversionError {
+    message: String,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+fn parse_bool(s: &str) -> Result<bool, ConversionError> {
+    match s {
+        "true" | "t" | "yes" | "y" | "1" => Ok(true),
+        "false" | "f" | "no" | "n" | "0" => Ok(false),
+        _ => Err(ConversionError {
+            message: format!("Unable to parse boolean: {:?}", s),
+        }),
+    }
+}
+
+impl Conversion {
+    pub fn convert(&self, value: ValueKind) -> Result<ValueKind, ConversionError> {
+        let string = value.to_string_lossy();
+
+        match self {
+            Conversion::Bytes => Ok(value),
+            Conversion::Integer => string
+                .parse::<i64>()
+                .map(ValueKind::Integer)
+                .map_err(|e| ConversionError {
+                    message: format!("Unable to parse integer: {}", e),
+                }),
+            Conversion::Float => string
+                .parse::<f64>()
+                .map(ValueKind::Float)
+                .map_err(|e| ConversionError {
+                    message: format!("Unable to parse float: {}", e),
+                }),
+            Conversion::Boolean => parse_bool(&string).map(ValueKind::Boolean),
+            Conversion::Timestamp(format) => {
+                let timestamp = match format {
+                    Some(format) => DateTime::parse_from_str(&string, format)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .map_err(|e| ConversionError {
+                            message: format!("Unable to parse timestamp {:?}: {}", format, e),
+                        })?,
+                    None => string.parse::<DateTime<Utc>>().map_err(|e| ConversionError {
+                        message: format!("Unable to parse timestamp: {}", e),
+                    })?,
+                };
+                Ok(ValueKind::Timestamp(timestamp))
+            }
+        }
+    }
+}
+
+pub fn parse_conversion_map(
+    types: &HashMap<Atom, String>,
+) -> Result<HashMap<Atom, Conversion>, String> {
+    types
+        .iter()
+        .map(|(field, typename)| {
+            typename
+                .parse::<Conversion>()
+                .map(|conv| (field.clone(), conv))
+                .map_err(|e| format!("Invalid type for field {:?}: {}", field, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_scalar_conversions() {
+        assert_eq!("bytes".parse(), Ok(Conversion::Bytes));
+        assert_eq!("int".parse(), Ok(Conversion::Integer));
+        assert_eq!("float".parse(), Ok(Conversion::Float));
+        assert_eq!("bool".parse(), Ok(Conversion::Boolean));
+    }
+
+    #[test]
+    fn parses_timestamp_conversion_with_format() {
+        assert_eq!(
+            "timestamp|%Y-%m-%dT%H:%M:%S%z".parse(),
+            Ok(Conversion::Timestamp(Some("%Y-%m-%dT%H:%M:%S%z".into())))
+        );
+        assert_eq!("timestamp".parse(), Ok(Conversion::Timestamp(None)));
+    }
+
+    #[test]
+    fn converts_timestamp_with_format() {
+        let converted = Conversion::Timestamp(Some("%Y-%m-%dT%H:%M:%S%z".into()))
+            .convert(ValueKind::Bytes("2020-01-02T03:04:05+0000".into()))
+            .unwrap();
+
+        match converted {
+            ValueKind::Timestamp(_) => {}
+            other => panic!("Expected ValueKind::Timestamp, got {:?}", other),
+        }
+    }
+}